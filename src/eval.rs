@@ -4,18 +4,46 @@ use std::{
     rc::Rc,
 };
 
-use crate::parser::{parse_program, Object};
+use crate::parser::{make_rational, parse_program, Object};
+
+pub type NativeFunc = fn(&VecDeque<Object>, &mut Rc<RefCell<Env>>) -> Result<Object, String>;
 
 pub struct Env {
     parent: Option<Rc<RefCell<Env>>>,
     vars: HashMap<String, Object>,
+    natives: HashMap<String, NativeFunc>,
 }
 
 impl Env {
     pub fn new() -> Self {
+        let mut natives: HashMap<String, NativeFunc> = HashMap::new();
+        natives.insert("+".to_string(), eval_binary_op as NativeFunc);
+        natives.insert("-".to_string(), eval_binary_op);
+        natives.insert("*".to_string(), eval_binary_op);
+        natives.insert("/".to_string(), eval_binary_op);
+        natives.insert("<".to_string(), eval_binary_op);
+        natives.insert(">".to_string(), eval_binary_op);
+        natives.insert("==".to_string(), eval_binary_op);
+        natives.insert("!=".to_string(), eval_binary_op);
+        natives.insert("define".to_string(), eval_define);
+        natives.insert("lambda".to_string(), eval_lambda);
+        natives.insert("macro".to_string(), eval_macro);
+        natives.insert("atom".to_string(), eval_atom);
+        natives.insert("quote".to_string(), eval_quote);
+        natives.insert("cons".to_string(), eval_cons);
+        natives.insert("car".to_string(), eval_car);
+        natives.insert("cdr".to_string(), eval_cdr);
+        natives.insert("str".to_string(), eval_str);
+        natives.insert("str-len".to_string(), eval_str_len);
+        natives.insert("str-cat".to_string(), eval_str_cat);
+        natives.insert("quasiquote".to_string(), eval_quasiquote_form);
+        natives.insert("eval".to_string(), eval_eval);
+        natives.insert("apply".to_string(), eval_apply);
+
         Self {
             parent: None,
             vars: HashMap::new(),
+            natives,
         }
     }
 
@@ -33,11 +61,120 @@ impl Env {
         self.vars.insert(name.to_string(), val);
     }
 
+    fn get_native(&self, name: &str) -> Option<NativeFunc> {
+        match self.natives.get(name) {
+            Some(f) => Some(*f),
+            None => match &self.parent {
+                Some(env) => env.borrow().get_native(name),
+                None => None,
+            },
+        }
+    }
+
     fn extend(parent: Rc<RefCell<Self>>) -> Self {
         Self {
             parent: Some(parent),
             vars: HashMap::new(),
+            natives: HashMap::new(),
+        }
+    }
+}
+
+/// A number at some rung of the `Integer -> Rational -> Float` tower.
+#[derive(Clone, Copy)]
+enum Num {
+    Integer(i64),
+    Rational(i64, i64),
+    Float(f64),
+}
+
+fn as_num(obj: &Object) -> Option<Num> {
+    match *obj {
+        Object::Integer(n) => Some(Num::Integer(n)),
+        Object::Rational(n, d) => Some(Num::Rational(n, d)),
+        Object::Float(n) => Some(Num::Float(n)),
+        _ => None,
+    }
+}
+
+fn to_float(n: Num) -> f64 {
+    match n {
+        Num::Integer(n) => n as f64,
+        Num::Rational(n, d) => n as f64 / d as f64,
+        Num::Float(n) => n,
+    }
+}
+
+fn to_rational(n: Num) -> (i64, i64) {
+    match n {
+        Num::Integer(n) => (n, 1),
+        Num::Rational(n, d) => (n, d),
+        Num::Float(_) => unreachable!("float is the top of the tower"),
+    }
+}
+
+/// Promotes `a` and `b` to a common rung of the tower so they can be
+/// combined directly.
+fn promote(a: Num, b: Num) -> (Num, Num) {
+    match (a, b) {
+        (Num::Float(_), _) | (_, Num::Float(_)) => (Num::Float(to_float(a)), Num::Float(to_float(b))),
+        (Num::Rational(_, _), _) | (_, Num::Rational(_, _)) => {
+            let (an, ad) = to_rational(a);
+            let (bn, bd) = to_rational(b);
+            (Num::Rational(an, ad), Num::Rational(bn, bd))
+        }
+        _ => (a, b),
+    }
+}
+
+fn numeric_add(left: Num, right: Num) -> Result<Object, String> {
+    match promote(left, right) {
+        (Num::Integer(x), Num::Integer(y)) => Ok(Object::Integer(x + y)),
+        (Num::Rational(xn, xd), Num::Rational(yn, yd)) => make_rational(xn * yd + yn * xd, xd * yd),
+        (Num::Float(x), Num::Float(y)) => Ok(Object::Float(x + y)),
+        _ => unreachable!("promote always returns a matching pair"),
+    }
+}
+
+fn numeric_sub(left: Num, right: Num) -> Result<Object, String> {
+    match promote(left, right) {
+        (Num::Integer(x), Num::Integer(y)) => Ok(Object::Integer(x - y)),
+        (Num::Rational(xn, xd), Num::Rational(yn, yd)) => make_rational(xn * yd - yn * xd, xd * yd),
+        (Num::Float(x), Num::Float(y)) => Ok(Object::Float(x - y)),
+        _ => unreachable!("promote always returns a matching pair"),
+    }
+}
+
+fn numeric_mul(left: Num, right: Num) -> Result<Object, String> {
+    match promote(left, right) {
+        (Num::Integer(x), Num::Integer(y)) => Ok(Object::Integer(x * y)),
+        (Num::Rational(xn, xd), Num::Rational(yn, yd)) => make_rational(xn * yn, xd * yd),
+        (Num::Float(x), Num::Float(y)) => Ok(Object::Float(x * y)),
+        _ => unreachable!("promote always returns a matching pair"),
+    }
+}
+
+fn numeric_div(left: Num, right: Num) -> Result<Object, String> {
+    match promote(left, right) {
+        (Num::Integer(x), Num::Integer(y)) => make_rational(x, y),
+        (Num::Rational(xn, xd), Num::Rational(yn, yd)) => make_rational(xn * yd, xd * yn),
+        (Num::Float(x), Num::Float(y)) => {
+            if y != 0.0 {
+                Ok(Object::Float(x / y))
+            } else {
+                Err("Connot divide by 0".to_string())
+            }
         }
+        _ => unreachable!("promote always returns a matching pair"),
+    }
+}
+
+fn numeric_cmp(left: Num, right: Num) -> std::cmp::Ordering {
+    match promote(left, right) {
+        (Num::Integer(x), Num::Integer(y)) => x.cmp(&y),
+        (Num::Rational(xn, xd), Num::Rational(yn, yd)) => (xn * yd).cmp(&(yn * xd)),
+        (Num::Float(x), Num::Float(y)) => x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal),
+        _ => unreachable!("promote always returns a matching pair"),
     }
 }
 
@@ -49,35 +186,23 @@ fn eval_binary_op(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result
     let op = list[0].clone();
     let left = {
         let obj = eval_object(&list[1], env)?;
-        match obj {
-            Object::Integer(n) => n,
-            _ => return Err(format!("Left operand must be an integer {:?}", obj)),
-        }
+        as_num(&obj).ok_or_else(|| format!("Left operand must be a number {:?}", obj))?
     };
     let right = {
         let obj = eval_object(&list[2], env)?;
-        match obj {
-            Object::Integer(n) => n,
-            _ => return Err(format!("Right operand must be an integer {:?}", obj)),
-        }
+        as_num(&obj).ok_or_else(|| format!("Right operand must be a number {:?}", obj))?
     };
 
     match op {
         Object::Symbol(s) => match s.as_str() {
-            "+" => Ok(Object::Integer(left + right)),
-            "-" => Ok(Object::Integer(left - right)),
-            "*" => Ok(Object::Integer(left * right)),
-            "/" => {
-                if right != 0 {
-                    Ok(Object::Integer(left / right))
-                } else {
-                    Err("Connot divide by 0".to_string())
-                }
-            }
-            "<" => Ok(Object::Bool(left < right)),
-            ">" => Ok(Object::Bool(left > right)),
-            "==" => Ok(Object::Bool(left == right)),
-            "!=" => Ok(Object::Bool(left != right)),
+            "+" => numeric_add(left, right),
+            "-" => numeric_sub(left, right),
+            "*" => numeric_mul(left, right),
+            "/" => numeric_div(left, right),
+            "<" => Ok(Object::Bool(numeric_cmp(left, right) == std::cmp::Ordering::Less)),
+            ">" => Ok(Object::Bool(numeric_cmp(left, right) == std::cmp::Ordering::Greater)),
+            "==" => Ok(Object::Bool(numeric_cmp(left, right) == std::cmp::Ordering::Equal)),
+            "!=" => Ok(Object::Bool(numeric_cmp(left, right) != std::cmp::Ordering::Equal)),
             _ => Err("Operator must be symbol".to_string()),
         },
         _ => Err("Operator must be a symbol".to_string()),
@@ -94,11 +219,11 @@ fn eval_define(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Ob
         _ => return Err("Invalid identifier for define".to_string()),
     };
     let val = eval_object(&list[2], env)?;
-    env.borrow_mut().set_object(&sym, val);
+    env.borrow_mut().set_object(sym, val);
     Ok(Object::Nil)
 }
 
-fn eval_if(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+fn eval_if_step(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<EvalFlow, String> {
     if list.len() != 4 {
         return Err("Invalid number of arguments for if statement".to_string());
     };
@@ -111,16 +236,16 @@ fn eval_if(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object
         }
     };
 
-    if cond {
-        eval_object(&list[2], env)
-    } else {
-        eval_object(&list[3], env)
-    }
+    let branch = if cond { list[2].clone() } else { list[3].clone() };
+    Ok(EvalFlow::Tail(branch, env.clone()))
 }
 
-fn eval_lambda(list: &VecDeque<Object>) -> Result<Object, String> {
+fn parse_params_and_body(
+    list: &VecDeque<Object>,
+    form: &str,
+) -> Result<(Vec<String>, VecDeque<Object>), String> {
     if list.len() != 3 {
-        return Err("Invalid number of arguments for lambda statement".to_string());
+        return Err(format!("Invalid number of arguments for {} statement", form));
     };
 
     let params = match &list[1] {
@@ -129,52 +254,179 @@ fn eval_lambda(list: &VecDeque<Object>) -> Result<Object, String> {
             for param in list {
                 match param {
                     Object::Symbol(s) => params.push(s.clone()),
-                    _ => return Err("Invalid lambda parameter: not symbol".to_string()),
+                    _ => return Err(format!("Invalid {} parameter: not symbol", form)),
                 }
             }
             params
         }
-        _ => return Err("Invalid lambda: first argument is not list".to_string()),
+        _ => return Err(format!("Invalid {}: first argument is not list", form)),
     };
 
     let body = match &list[2] {
         Object::List(list) => list.clone(),
-        _ => return Err("Invalid lambda: body is not list".to_string()),
+        _ => return Err(format!("Invalid {}: body is not list", form)),
     };
 
-    Ok(Object::Lambda(params, body))
+    Ok((params, body))
+}
+
+fn eval_lambda(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+    let (params, body) = parse_params_and_body(list, "lambda")?;
+    Ok(Object::Lambda(params, body, env.clone()))
+}
+
+fn eval_macro(list: &VecDeque<Object>, _env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+    let (params, body) = parse_params_and_body(list, "macro")?;
+    Ok(Object::Macro(params, body))
+}
+
+/// Binds `params` to `args` in `env`, handling a trailing `&rest name`
+/// by collecting the remaining arguments into a single `List` (or `Nil`
+/// if there are none).
+///
+/// For a macro, `&rest`-bound forms stay wrapped in that `List` just like
+/// any other value bound by `bind_params` — expansion binds each argument
+/// form as-is and evaluates the macro body, it does not implicitly splice a `List` into the surrounding template. A
+/// macro body that wants each collected form to land as its own argument
+/// (e.g. `(progn ...)` wrapping a variadic function body) must build the
+/// template with `quasiquote`/`unquote-splicing` (`,@body`), the same way
+/// any other multi-form splice is written.
+fn bind_params(
+    params: &[String],
+    mut args: VecDeque<Object>,
+    env: &mut Rc<RefCell<Env>>,
+) -> Result<(), String> {
+    match params.iter().position(|p| p == "&rest") {
+        Some(rest_pos) => {
+            let rest_name = params
+                .get(rest_pos + 1)
+                .ok_or("Missing parameter name after &rest")?;
+            if args.len() < rest_pos {
+                return Err("Invalid number of arguments for function call".to_string());
+            }
+
+            for param in &params[..rest_pos] {
+                let val = args.pop_front().unwrap();
+                env.borrow_mut().set_object(param, val);
+            }
+            let rest = if args.is_empty() {
+                Object::Nil
+            } else {
+                Object::List(args)
+            };
+            env.borrow_mut().set_object(rest_name, rest);
+        }
+        None => {
+            if params.len() != args.len() {
+                return Err("Invalid number of arguments for function call".to_string());
+            }
+            for (param, val) in params.iter().zip(args) {
+                env.borrow_mut().set_object(param, val);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Calls a lambda by extending its captured `closure_env` (the environment
+/// live when the `lambda` form was evaluated), not the caller's environment.
+/// This keeps the chain `get_object`/`get_native` walk at a fixed depth no
+/// matter how deep a self-recursive call nests, instead of growing it by one
+/// link per call.
+fn call_lambda(
+    params: &[String],
+    body: &VecDeque<Object>,
+    args: VecDeque<Object>,
+    closure_env: &Rc<RefCell<Env>>,
+) -> Result<Object, String> {
+    let mut new_env = Rc::new(RefCell::new(Env::extend(closure_env.clone())));
+    bind_params(params, args, &mut new_env)?;
+    eval_object(&Object::List(body.clone()), &mut new_env)
+}
+
+fn tail_call_lambda(
+    params: &[String],
+    body: &VecDeque<Object>,
+    args: VecDeque<Object>,
+    closure_env: &Rc<RefCell<Env>>,
+) -> Result<EvalFlow, String> {
+    let mut new_env = Rc::new(RefCell::new(Env::extend(closure_env.clone())));
+    bind_params(params, args, &mut new_env)?;
+    Ok(EvalFlow::Tail(Object::List(body.clone()), new_env))
 }
 
-fn eval_func_call(
+fn expand_macro(
+    params: &[String],
+    body: &VecDeque<Object>,
+    args: VecDeque<Object>,
+    env: &mut Rc<RefCell<Env>>,
+) -> Result<Object, String> {
+    let mut expand_env = Rc::new(RefCell::new(Env::extend(env.clone())));
+    bind_params(params, args, &mut expand_env)?;
+    eval_object(&Object::List(body.clone()), &mut expand_env)
+}
+
+fn eval_func_call_step(
     name: &str,
     list: &VecDeque<Object>,
     env: &mut Rc<RefCell<Env>>,
-) -> Result<Object, String> {
-    let func = {
-        let lambda = env.borrow().get_object(name);
-        if lambda.is_none() {
-            return Err(format!("Unbound func: {}", name));
-        };
-        lambda.unwrap()
-    };
+) -> Result<EvalFlow, String> {
+    let func = env
+        .borrow()
+        .get_object(name)
+        .ok_or_else(|| format!("Unbound func: {}", name))?;
 
     match func {
-        Object::Lambda(params, body) => {
-            if params.len() != list.len() - 1 {
-                return Err(format!(
-                    "Invalid call of function `{}`: number of arguments is not correct",
-                    name
-                ));
+        Object::Lambda(params, body, closure_env) => {
+            let mut args = VecDeque::new();
+            for arg in list.iter().skip(1) {
+                args.push_back(eval_object(arg, env)?);
             }
+            tail_call_lambda(&params, &body, args, &closure_env)
+        }
+        Object::Macro(params, body) => {
+            let args: VecDeque<Object> = list.iter().skip(1).cloned().collect();
+            let expanded = expand_macro(&params, &body, args, env)?;
+            Ok(EvalFlow::Tail(expanded, env.clone()))
+        }
+        _ => Err(format!("Not a lambda: {}", name)),
+    }
+}
+
+fn eval_eval(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+    if list.len() != 2 {
+        return Err("Invalid number of arguments for eval".to_string());
+    }
+
+    let val = eval_object(&list[1], env)?;
+    eval_object(&val, env)
+}
+
+fn eval_apply(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+    if list.len() != 3 {
+        return Err("Invalid number of arguments for apply".to_string());
+    }
 
-            let mut new_env = Rc::new(RefCell::new(Env::extend(env.clone())));
-            for (i, param) in params.iter().enumerate() {
-                let val = eval_object(&list[i + 1], env)?;
-                new_env.borrow_mut().set_object(param, val);
+    let func = eval_object(&list[1], env)?;
+    let args = match eval_object(&list[2], env)? {
+        Object::List(l) => l,
+        Object::Nil => VecDeque::new(),
+        other => return Err(format!("apply: second argument must be a list, found {}", other)),
+    };
+
+    match func {
+        Object::Lambda(params, body, closure_env) => call_lambda(&params, &body, args, &closure_env),
+        Object::Symbol(name) => {
+            let resolved = env.borrow().get_object(&name);
+            match resolved {
+                Some(Object::Lambda(params, body, closure_env)) => {
+                    call_lambda(&params, &body, args, &closure_env)
+                }
+                Some(other) => Err(format!("Not a lambda: {}", other)),
+                None => Err(format!("Unbound func: {}", name)),
             }
-            eval_object(&Object::List(body), &mut new_env)
         }
-        _ => Err(format!("Not a lambda: {}", name)),
+        other => Err(format!("apply: first argument must be a function, found {}", other)),
     }
 }
 
@@ -184,9 +436,13 @@ fn eval_atom(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Obje
     }
 
     match eval_object(&list[1], env)? {
-        Object::Nil | Object::Bool(_) | Object::Integer(_) | Object::Symbol(_) => {
-            Ok(Object::Bool(true))
-        }
+        Object::Nil
+        | Object::Bool(_)
+        | Object::Integer(_)
+        | Object::Float(_)
+        | Object::Rational(_, _)
+        | Object::Symbol(_)
+        | Object::Str(_) => Ok(Object::Bool(true)),
         _ => Ok(Object::Bool(false)),
     }
 }
@@ -206,7 +462,7 @@ fn eval_symbol(name: &str, env: &mut Rc<RefCell<Env>>) -> Result<Object, String>
     }
 }
 
-fn eval_quote(list: &VecDeque<Object>) -> Result<Object, String> {
+fn eval_quote(list: &VecDeque<Object>, _env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
     if list.len() != 2 {
         return Err("Invalid number of arguments for quote statement".to_string());
     }
@@ -268,35 +524,150 @@ fn eval_cdr(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Objec
     }
 }
 
-fn eval_progn(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
-    let mut res = Ok(Object::Nil);
-    for i in 1..list.len() {
-        res = eval_object(&list[i], env);
+fn eval_str(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+    if list.len() != 2 {
+        return Err("Invalid number of arguments for str".to_string());
+    }
+
+    let val = eval_object(&list[1], env)?;
+    match val {
+        Object::Str(s) => Ok(Object::Str(s)),
+        other => Ok(Object::Str(format!("{}", other))),
+    }
+}
+
+fn eval_str_len(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+    if list.len() != 2 {
+        return Err("Invalid number of arguments for str-len".to_string());
+    }
+
+    match eval_object(&list[1], env)? {
+        Object::Str(s) => Ok(Object::Integer(s.chars().count() as i64)),
+        obj => Err(format!("Invalid str-len: argument is not a string, found {}", obj)),
+    }
+}
+
+fn eval_str_cat(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+    let mut result = String::new();
+    for arg in list.iter().skip(1) {
+        match eval_object(arg, env)? {
+            Object::Str(s) => result.push_str(&s),
+            obj => return Err(format!("Invalid str-cat: argument is not a string, found {}", obj)),
+        }
+    }
+    Ok(Object::Str(result))
+}
+
+fn eval_quasiquote_form(
+    list: &VecDeque<Object>,
+    env: &mut Rc<RefCell<Env>>,
+) -> Result<Object, String> {
+    if list.len() != 2 {
+        return Err("Invalid number of arguments for quasiquote".to_string());
     }
-    res
+
+    eval_quasiquote(&list[1], 0, env)
 }
 
-fn eval_list(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
+fn eval_quasiquote(
+    obj: &Object,
+    depth: i64,
+    env: &mut Rc<RefCell<Env>>,
+) -> Result<Object, String> {
+    let list = match obj {
+        Object::List(list) => list,
+        other => return Ok(other.clone()),
+    };
+
+    if let Some(Object::Symbol(s)) = list.front() {
+        if s == "unquote" && list.len() == 2 {
+            return if depth == 0 {
+                eval_object(&list[1], env)
+            } else {
+                wrap(
+                    "unquote",
+                    eval_quasiquote(&list[1], depth - 1, env)?,
+                )
+            };
+        }
+        if s == "quasiquote" && list.len() == 2 {
+            return wrap(
+                "quasiquote",
+                eval_quasiquote(&list[1], depth + 1, env)?,
+            );
+        }
+    }
+
+    let mut new_list = VecDeque::new();
+    for item in list {
+        if depth == 0
+            && let Object::List(inner) = item
+            && let Some(Object::Symbol(s)) = inner.front()
+            && s == "unquote-splicing"
+            && inner.len() == 2
+        {
+            match eval_object(&inner[1], env)? {
+                Object::List(spliced) => {
+                    new_list.extend(spliced);
+                    continue;
+                }
+                Object::Nil => continue,
+                other => {
+                    return Err(format!(
+                        "unquote-splicing requires a list, found {}",
+                        other
+                    ))
+                }
+            }
+        }
+        new_list.push_back(eval_quasiquote(item, depth, env)?);
+    }
+    Ok(Object::List(new_list))
+}
+
+fn wrap(sym: &str, obj: Object) -> Result<Object, String> {
+    Ok(Object::List(VecDeque::from([
+        Object::Symbol(sym.to_string()),
+        obj,
+    ])))
+}
+
+fn eval_progn_step(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<EvalFlow, String> {
+    if list.len() == 1 {
+        return Ok(EvalFlow::Value(Object::Nil));
+    }
+
+    for form in list.iter().take(list.len() - 1).skip(1) {
+        eval_object(form, env)?;
+    }
+    Ok(EvalFlow::Tail(list[list.len() - 1].clone(), env.clone()))
+}
+
+/// Result of evaluating one step of a form: either a final value, or a tail
+/// position to evaluate next without recursing, so self-recursive functions
+/// run in constant Rust stack.
+enum EvalFlow {
+    Value(Object),
+    Tail(Object, Rc<RefCell<Env>>),
+}
+
+fn eval_list_step(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<EvalFlow, String> {
     if list.is_empty() {
-        return Ok(Object::Nil);
+        return Ok(EvalFlow::Value(Object::Nil));
     }
 
     let head = &list[0];
     match head {
         Object::Symbol(s) => match s.as_str() {
-            "+" | "-" | "*" | "/" | "<" | ">" | "==" | "!=" => {
-                return eval_binary_op(&list, env);
+            "if" => eval_if_step(list, env),
+            "progn" => eval_progn_step(list, env),
+            _ => {
+                let native = env.borrow().get_native(s);
+                match native {
+                    Some(f) => Ok(EvalFlow::Value(f(list, env)?)),
+                    None => eval_func_call_step(s, list, env),
+                }
             }
-            "define" => eval_define(&list, env),
-            "if" => eval_if(&list, env),
-            "lambda" => eval_lambda(&list),
-            "atom" => eval_atom(&list, env),
-            "quote" => eval_quote(&list),
-            "cons" => eval_cons(&list, env),
-            "car" => eval_car(&list, env),
-            "cdr" => eval_cdr(&list, env),
-            "progn" => eval_progn(&list, env),
-            _ => eval_func_call(&s, &list, env),
         },
         _ => {
             let mut new_list = VecDeque::new();
@@ -307,23 +678,74 @@ fn eval_list(list: &VecDeque<Object>, env: &mut Rc<RefCell<Env>>) -> Result<Obje
                     _ => new_list.push_back(res),
                 }
             }
-            Ok(Object::List(new_list))
+            Ok(EvalFlow::Value(Object::List(new_list)))
         }
     }
 }
 
 fn eval_object(obj: &Object, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
-    match obj {
-        Object::Nil => Ok(Object::Nil),
-        Object::Integer(n) => Ok(Object::Integer(*n)),
-        Object::Bool(b) => Ok(Object::Bool(*b)),
-        Object::Symbol(name) => eval_symbol(name, env),
-        Object::Lambda(_, _) => Ok(Object::Nil),
-        Object::List(list) => eval_list(list, env),
+    let mut obj = obj.clone();
+    let mut env = env.clone();
+    loop {
+        let flow = match &obj {
+            Object::Nil => return Ok(Object::Nil),
+            Object::Integer(n) => return Ok(Object::Integer(*n)),
+            Object::Float(n) => return Ok(Object::Float(*n)),
+            Object::Rational(n, d) => return Ok(Object::Rational(*n, *d)),
+            Object::Bool(b) => return Ok(Object::Bool(*b)),
+            Object::Str(s) => return Ok(Object::Str(s.clone())),
+            Object::Symbol(name) => return eval_symbol(name, &mut env),
+            Object::Lambda(_, _, _) => return Ok(Object::Nil),
+            Object::Macro(_, _) => return Ok(Object::Nil),
+            Object::List(list) => eval_list_step(list, &mut env)?,
+        };
+
+        match flow {
+            EvalFlow::Value(val) => return Ok(val),
+            EvalFlow::Tail(next_obj, next_env) => {
+                obj = next_obj;
+                env = next_env;
+            }
+        }
     }
 }
 
 pub fn eval(program: &str, env: &mut Rc<RefCell<Env>>) -> Result<Object, String> {
-    let parsed_list = parse_program(program)?;
-    eval_object(&parsed_list, env)
+    let forms = parse_program(program)?;
+    let mut result = Object::Nil;
+    for form in &forms {
+        result = eval_object(form, env)?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFUN: &str = "(define defun
+        (macro (name params &rest body)
+            (quasiquote (define (unquote name)
+                (lambda (unquote params)
+                    (progn (unquote-splicing body)))))))";
+
+    fn run(program: &str) -> Object {
+        let mut env = Rc::new(RefCell::new(Env::new()));
+        eval(program, &mut env).expect("eval failed")
+    }
+
+    #[test]
+    fn defun_macro_defines_and_calls_a_function() {
+        let program = format!("{} (defun square (x) (* x x)) (square 5)", DEFUN);
+        assert_eq!(run(&program), Object::Integer(25));
+    }
+
+    #[test]
+    fn defun_macro_runs_every_body_form_in_order() {
+        let program = format!(
+            "{} (defun two-forms (x) (define y (+ x 1)) (* y 2)) (two-forms 5)",
+            DEFUN
+        );
+        assert_eq!(run(&program), Object::Integer(12));
+    }
 }
@@ -1,31 +1,106 @@
 #[derive(PartialEq, Debug, Clone)]
 pub enum Token {
     Integer(i64),
+    Float(f64),
+    Rational(i64, i64),
     Symbol(String),
+    Str(String),
     LParen,
     RParen,
     Quote,
+    Quasiquote,
+    Unquote,
+    UnquoteSplicing,
 }
 
-pub fn lex(program: &str) -> Vec<Token> {
-    let prog2 = program
-        .replace("(", " ( ")
-        .replace(")", " ) ")
-        .replace("\'", " \' ");
-    let words = prog2.split_whitespace();
+fn lex_rational(word: &str) -> Option<Token> {
+    let (num, den) = word.split_once('/')?;
+    Some(Token::Rational(num.parse().ok()?, den.parse().ok()?))
+}
+
+fn lex_string(chars: &[char], start: usize) -> (String, usize) {
+    let mut s = String::new();
+    let mut i = start;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return (s, i + 1),
+            '\\' if i + 1 < chars.len() => {
+                let escaped = match chars[i + 1] {
+                    'n' => '\n',
+                    't' => '\t',
+                    '\\' => '\\',
+                    '"' => '"',
+                    other => other,
+                };
+                s.push(escaped);
+                i += 2;
+            }
+            c => {
+                s.push(c);
+                i += 1;
+            }
+        }
+    }
+    (s, i)
+}
 
+pub fn lex(program: &str) -> Vec<Token> {
+    let chars: Vec<char> = program.chars().collect();
     let mut tokens: Vec<Token> = vec![];
-    for word in words {
-        match word {
-            "(" => tokens.push(Token::LParen),
-            ")" => tokens.push(Token::RParen),
-            "\'" => tokens.push(Token::Quote),
+
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '\'' => {
+                tokens.push(Token::Quote);
+                i += 1;
+            }
+            '`' => {
+                tokens.push(Token::Quasiquote);
+                i += 1;
+            }
+            ',' => {
+                if i + 1 < chars.len() && chars[i + 1] == '@' {
+                    tokens.push(Token::UnquoteSplicing);
+                    i += 2;
+                } else {
+                    tokens.push(Token::Unquote);
+                    i += 1;
+                }
+            }
+            '"' => {
+                let (s, next) = lex_string(&chars, i + 1);
+                tokens.push(Token::Str(s));
+                i = next;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+            }
             _ => {
-                let i = word.parse::<i64>();
-                if i.is_ok() {
-                    tokens.push(Token::Integer(i.unwrap()));
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !"()'`,\"".contains(chars[i])
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if let Ok(n) = word.parse::<i64>() {
+                    tokens.push(Token::Integer(n));
+                } else if let Some(tok) = lex_rational(&word) {
+                    tokens.push(tok);
+                } else if let Ok(n) = word.parse::<f64>() {
+                    tokens.push(Token::Float(n));
                 } else {
-                    tokens.push(Token::Symbol(word.to_string()));
+                    tokens.push(Token::Symbol(word));
                 }
             }
         }
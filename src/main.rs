@@ -1,56 +1,19 @@
-use std::{cell::RefCell, error::Error, rc::Rc};
+use std::{cell::RefCell, env, error::Error, fs, rc::Rc};
 
 use eval::{eval, Env};
+use lexer::{lex, Token};
 use parser::Object;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
 
 mod eval;
 mod lexer;
 mod parser;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let mut env = Rc::new(RefCell::new(Env::new()));
-    let program = "(progn
-            (define map
-                (lambda (f l)
-                    (if (atom l)
-                        NIL
-                        (cons
-                            (f (car l))
-                            (map f (cdr l))
-                        )
-                    )
-                )
-            )
-            (define g
-                (lambda (a &rest x) (progn x)))
-            (define K 7)
-            (define mulK
-                (lambda (x)
-                    (progn
-                        (define L (+ K 1))
-                        (* x L)
-                    )
-                )
-            )
-            (define defun
-                (macro (name params &rest body)
-                    ('define name
-                        ('lambda params
-                            ('progn body)
-                        )
-                    )
-                )
-            )
-            (g 'a 'b 'c)
-        )";
-
-    let val = eval(program, &mut env)?;
+fn print_result(val: &Object) {
     match val {
         Object::Nil => {}
-        Object::Integer(n) => println!("{}", n),
-        Object::Bool(b) => println!("{}", b),
-        Object::Symbol(s) => println!("{}", s),
-        Object::Lambda(params, body) => {
+        Object::Lambda(params, body, _) => {
             println!("Lambda(");
             for param in params {
                 print!("{} ", param);
@@ -72,5 +35,65 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
         _ => println!("{}", val),
     }
+}
+
+/// Net count of unmatched `(` in `line`, used to tell whether the REPL
+/// should keep reading more lines before handing the buffer to `eval`.
+fn paren_balance(line: &str) -> i64 {
+    lex(line).iter().fold(0, |depth, token| match token {
+        Token::LParen => depth + 1,
+        Token::RParen => depth - 1,
+        _ => depth,
+    })
+}
+
+fn run_script(path: &str, env: &mut Rc<RefCell<Env>>) -> Result<(), Box<dyn Error>> {
+    let program = fs::read_to_string(path)?;
+    let val = eval(&program, env)?;
+    print_result(&val);
+    Ok(())
+}
+
+fn run_repl(env: &mut Rc<RefCell<Env>>) -> Result<(), Box<dyn Error>> {
+    let mut editor = DefaultEditor::new()?;
+    let mut buffer = String::new();
+    let mut depth = 0i64;
+
+    loop {
+        let prompt = if buffer.is_empty() { "rulisp> " } else { "...> " };
+        match editor.readline(prompt) {
+            Ok(line) => {
+                depth += paren_balance(&line);
+                buffer.push_str(&line);
+                buffer.push('\n');
+
+                if depth > 0 {
+                    continue;
+                }
+
+                editor.add_history_entry(buffer.trim())?;
+                match eval(&buffer, env) {
+                    Ok(val) => print_result(&val),
+                    Err(e) => eprintln!("Error: {}", e),
+                }
+                buffer.clear();
+                depth = 0;
+            }
+            Err(ReadlineError::Interrupted) => {
+                buffer.clear();
+                depth = 0;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(Box::new(e)),
+        }
+    }
     Ok(())
 }
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut env = Rc::new(RefCell::new(Env::new()));
+    match env::args().nth(1) {
+        Some(path) => run_script(&path, &mut env),
+        None => run_repl(&mut env),
+    }
+}
@@ -1,26 +1,77 @@
 use core::fmt;
-use std::collections::VecDeque;
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
 
+use crate::eval::Env;
 use crate::lexer::{lex, Token};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
 pub enum Object {
     Nil,
     Integer(i64),
+    Float(f64),
+    Rational(i64, i64),
     Bool(bool),
     Symbol(String),
-    Lambda(Vec<String>, VecDeque<Object>),
+    Str(String),
+    Lambda(Vec<String>, VecDeque<Object>, Rc<RefCell<Env>>),
+    Macro(Vec<String>, VecDeque<Object>),
     List(VecDeque<Object>),
 }
 
+impl fmt::Debug for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Object::Nil => write!(f, "Nil"),
+            Object::Integer(n) => write!(f, "Integer({:?})", n),
+            Object::Float(n) => write!(f, "Float({:?})", n),
+            Object::Rational(n, d) => write!(f, "Rational({:?}, {:?})", n, d),
+            Object::Bool(b) => write!(f, "Bool({:?})", b),
+            Object::Symbol(s) => write!(f, "Symbol({:?})", s),
+            Object::Str(s) => write!(f, "Str({:?})", s),
+            Object::Lambda(params, body, _) => write!(f, "Lambda({:?}, {:?})", params, body),
+            Object::Macro(params, body) => write!(f, "Macro({:?}, {:?})", params, body),
+            Object::List(list) => write!(f, "List({:?})", list),
+        }
+    }
+}
+
+impl PartialEq for Object {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Object::Nil, Object::Nil) => true,
+            (Object::Integer(a), Object::Integer(b)) => a == b,
+            (Object::Float(a), Object::Float(b)) => a == b,
+            (Object::Rational(an, ad), Object::Rational(bn, bd)) => an == bn && ad == bd,
+            (Object::Bool(a), Object::Bool(b)) => a == b,
+            (Object::Symbol(a), Object::Symbol(b)) => a == b,
+            (Object::Str(a), Object::Str(b)) => a == b,
+            (Object::Lambda(ap, ab, ae), Object::Lambda(bp, bb, be)) => {
+                ap == bp && ab == bb && Rc::ptr_eq(ae, be)
+            }
+            (Object::Macro(ap, ab), Object::Macro(bp, bb)) => ap == bp && ab == bb,
+            (Object::List(a), Object::List(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 impl fmt::Display for Object {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Object::Nil => write!(f, "NIL"),
             Object::Integer(n) => write!(f, "{}", n),
+            Object::Float(n) => {
+                if n.is_finite() && n.fract() == 0.0 {
+                    write!(f, "{:.1}", n)
+                } else {
+                    write!(f, "{}", n)
+                }
+            }
+            Object::Rational(n, d) => write!(f, "{}/{}", n, d),
             Object::Bool(b) => write!(f, "{}", b),
             Object::Symbol(s) => write!(f, "{}", s),
-            Object::Lambda(params, body) => {
+            Object::Str(s) => write!(f, "\"{}\"", s),
+            Object::Lambda(params, body, _) => {
                 write!(f, "Lambda(")?;
                 for param in params {
                     write!(f, "{} ", param)?;
@@ -31,6 +82,17 @@ impl fmt::Display for Object {
                 }
                 Ok(())
             }
+            Object::Macro(params, body) => {
+                write!(f, "Macro(")?;
+                for param in params {
+                    write!(f, "{} ", param)?;
+                }
+                write!(f, ")")?;
+                for expr in body {
+                    write!(f, " {}", expr)?;
+                }
+                Ok(())
+            }
             Object::List(list) => {
                 write!(f, "(")?;
                 for (i, obj) in list.iter().enumerate() {
@@ -45,6 +107,63 @@ impl fmt::Display for Object {
     }
 }
 
+/// Reduces `num/den` to lowest terms with a positive denominator, rejecting
+/// a zero denominator and collapsing whole-valued results to `Integer`.
+pub(crate) fn make_rational(num: i64, den: i64) -> Result<Object, String> {
+    if den == 0 {
+        return Err("Connot divide by 0".to_string());
+    }
+    let g = gcd(num, den).ok_or("Rational overflow")?;
+    let (mut n, mut d) = (num / g, den / g);
+    if d < 0 {
+        n = n.checked_neg().ok_or("Rational overflow")?;
+        d = d.checked_neg().ok_or("Rational overflow")?;
+    }
+    if d == 1 {
+        Ok(Object::Integer(n))
+    } else {
+        Ok(Object::Rational(n, d))
+    }
+}
+
+/// `None` on overflow (e.g. `gcd(i64::MIN, -1)`, where `i64::MIN % -1`
+/// can't be represented), so callers can report an error instead of
+/// unwinding on a valid-looking but unrepresentable rational.
+fn gcd(a: i64, b: i64) -> Option<i64> {
+    if b == 0 {
+        a.checked_abs()
+    } else {
+        gcd(b, a.checked_rem(b)?)
+    }
+}
+
+fn parse_next(tokens: &mut Vec<Token>) -> Result<Object, String> {
+    match tokens.pop() {
+        Some(Token::Integer(n)) => Ok(Object::Integer(n)),
+        Some(Token::Float(n)) => Ok(Object::Float(n)),
+        Some(Token::Rational(n, d)) => make_rational(n, d),
+        Some(Token::Symbol(s)) => Ok(Object::Symbol(s)),
+        Some(Token::Str(s)) => Ok(Object::Str(s)),
+        Some(Token::LParen) => {
+            tokens.push(Token::LParen);
+            parse(tokens)
+        }
+        Some(Token::Quote) => parse_prefixed(tokens, "quote"),
+        Some(Token::Quasiquote) => parse_prefixed(tokens, "quasiquote"),
+        Some(Token::Unquote) => parse_prefixed(tokens, "unquote"),
+        Some(Token::UnquoteSplicing) => parse_prefixed(tokens, "unquote-splicing"),
+        other => Err(format!("Invalid token after reader macro: {:?}", other)),
+    }
+}
+
+fn parse_prefixed(tokens: &mut Vec<Token>, sym: &str) -> Result<Object, String> {
+    let next_obj = parse_next(tokens)?;
+    let mut new_list = VecDeque::new();
+    new_list.push_back(Object::Symbol(sym.to_string()));
+    new_list.push_back(next_obj);
+    Ok(Object::List(new_list))
+}
+
 fn parse(tokens: &mut Vec<Token>) -> Result<Object, String> {
     let token = tokens.pop();
     if Some(Token::LParen) != token {
@@ -56,7 +175,10 @@ fn parse(tokens: &mut Vec<Token>) -> Result<Object, String> {
     while let Some(token) = tokens.pop() {
         match token {
             Token::Integer(n) => list.push_back(Object::Integer(n)),
+            Token::Float(n) => list.push_back(Object::Float(n)),
+            Token::Rational(n, d) => list.push_back(make_rational(n, d)?),
             Token::Symbol(s) => list.push_back(Object::Symbol(s)),
+            Token::Str(s) => list.push_back(Object::Str(s)),
             Token::LParen => {
                 tokens.push(Token::LParen);
                 let sub = parse(tokens)?;
@@ -65,32 +187,35 @@ fn parse(tokens: &mut Vec<Token>) -> Result<Object, String> {
             Token::RParen => {
                 return Ok(Object::List(list));
             }
-            Token::Quote => {
-                if let Some(next) = tokens.pop() {
-                    let next_obj = match next {
-                        Token::Integer(n) => Object::Integer(n),
-                        Token::Symbol(s) => Object::Symbol(s),
-                        Token::LParen => {
-                            tokens.push(Token::LParen);
-                            parse(tokens)?
-                        }
-                        _ => return Err("Invalid quote".to_string()),
-                    };
-                    let mut new_list = VecDeque::new();
-                    new_list.push_back(Object::Symbol("quote".to_string()));
-                    new_list.push_back(next_obj);
-                    list.push_back(Object::List(new_list));
-                }
-            }
+            Token::Quote => list.push_back(parse_prefixed(tokens, "quote")?),
+            Token::Quasiquote => list.push_back(parse_prefixed(tokens, "quasiquote")?),
+            Token::Unquote => list.push_back(parse_prefixed(tokens, "unquote")?),
+            Token::UnquoteSplicing => list.push_back(parse_prefixed(tokens, "unquote-splicing")?),
         }
     }
 
     Ok(Object::List(list))
 }
 
-pub fn parse_program(program: &str) -> Result<Object, String> {
+/// Parses every top-level form in `program` in sequence, not just the
+/// first, so a script can hold multiple `define`s or expressions back to
+/// back without being wrapped in an explicit `(progn ...)`.
+pub fn parse_program(program: &str) -> Result<Vec<Object>, String> {
     let tokens = lex(program);
-    let mut tokens = tokens.into_iter().rev().collect();
-    let parsed_list = parse(&mut tokens)?;
-    Ok(parsed_list)
+    let mut tokens: Vec<Token> = tokens.into_iter().rev().collect();
+    let mut forms = Vec::new();
+    while !tokens.is_empty() {
+        forms.push(parse_next(&mut tokens)?);
+    }
+    Ok(forms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn make_rational_reports_an_error_instead_of_panicking_on_overflow() {
+        assert!(make_rational(i64::MIN, -1).is_err());
+    }
 }